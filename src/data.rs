@@ -0,0 +1,359 @@
+//! Storage for a [`Stamp`]'s pixel data, and [`StampBuf`], its owned, mutable
+//! counterpart.
+
+#[cfg(not(feature = "progmem"))]
+use alloc::vec;
+#[cfg(not(feature = "progmem"))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "progmem")]
+use avr_progmem::wrapper::ProgMem;
+
+use crate::rle::RunCursor;
+#[cfg(not(feature = "progmem"))]
+use crate::{Color, Stamp};
+
+/// Raw byte storage backing a [`Stamp`](crate::Stamp), in whichever representation
+/// [`Data`] wraps it in.
+///
+/// Under the `progmem` feature (and only on `avr` targets), bytes are read out of
+/// program memory instead of RAM; everywhere else this is a thin wrapper around a raw
+/// pointer.
+#[derive(Debug, Clone)]
+pub(crate) struct Bytes {
+    #[cfg(not(feature = "progmem"))]
+    ptr: *const u8,
+    #[cfg(feature = "progmem")]
+    ptr: ProgMem<u8>,
+}
+
+impl Bytes {
+    const unsafe fn from_raw(data: *const u8) -> Self {
+        #[cfg(not(feature = "progmem"))]
+        {
+            Self { ptr: data }
+        }
+        #[cfg(feature = "progmem")]
+        {
+            Self {
+                ptr: ProgMem::new_at(data),
+            }
+        }
+    }
+
+    pub(crate) unsafe fn get_unchecked(&self, index: usize) -> u8 {
+        #[cfg(not(feature = "progmem"))]
+        {
+            *self.ptr.add(index)
+        }
+        #[cfg(feature = "progmem")]
+        {
+            self.ptr.load_at(index)
+        }
+    }
+}
+
+/// A [`Stamp`](crate::Stamp)'s pixel data, in one of two on-disk representations.
+#[derive(Debug, Clone)]
+pub(crate) enum Data {
+    /// One bit per pixel, MSB-first, with no padding between rows.
+    Flat(Bytes),
+    /// [`crate::rle`]-compressed: a stream of alternating Black/White run lengths,
+    /// always starting with a (possibly zero-length) Black run.
+    Rle(Bytes),
+}
+
+impl Data {
+    pub(crate) const unsafe fn from_raw(data: *const u8) -> Self {
+        Self::Flat(Bytes::from_raw(data))
+    }
+
+    pub(crate) const unsafe fn from_raw_rle(data: *const u8) -> Self {
+        Self::Rle(Bytes::from_raw(data))
+    }
+
+    /// Yields the bit (`true` is white) at the given pixel index. For [`Data::Rle`],
+    /// this walks runs from the very start of the stream every time, which is the
+    /// price of random access into a variable-length encoding; sequential access
+    /// should go through [`Data::bits`] instead.
+    pub(crate) unsafe fn get_bit(&self, index: usize) -> bool {
+        match self {
+            Data::Flat(bytes) => {
+                let byte = bytes.get_unchecked(index / 8);
+                let mask = 0b10000000 >> (index % 8);
+                byte & mask != 0
+            }
+            Data::Rle(bytes) => crate::rle::bit_at(bytes, index),
+        }
+    }
+
+    /// Returns a cursor that yields this data's bits in sequence, in amortized O(1)
+    /// per bit even for [`Data::Rle`].
+    pub(crate) fn bits(&self) -> Bits<'_> {
+        match self {
+            Data::Flat(bytes) => Bits::Flat { bytes, index: 0 },
+            Data::Rle(bytes) => Bits::Rle {
+                bytes,
+                cursor: RunCursor::new(),
+            },
+        }
+    }
+}
+
+/// Sequential bit cursor produced by [`Data::bits`].
+#[derive(Debug, Clone)]
+pub(crate) enum Bits<'a> {
+    Flat { bytes: &'a Bytes, index: usize },
+    Rle { bytes: &'a Bytes, cursor: RunCursor },
+}
+
+impl Bits<'_> {
+    /// Yields the next bit (`true` is white) in sequence.
+    ///
+    /// # Safety
+    ///
+    /// The caller must not request more bits than the underlying stamp has pixels.
+    pub(crate) unsafe fn next_bit(&mut self) -> bool {
+        match self {
+            Bits::Flat { bytes, index } => {
+                let idx = *index;
+                *index += 1;
+                let byte = bytes.get_unchecked(idx / 8);
+                let mask = 0b10000000 >> (idx % 8);
+                byte & mask != 0
+            }
+            Bits::Rle { bytes, cursor } => cursor.next_bit(bytes),
+        }
+    }
+}
+
+/// Owned, mutable counterpart to [`Stamp`].
+///
+/// Where `Stamp` is backed by a `'static` pointer &mdash; perfect for compile-time
+/// assets, but useless for drawing into at runtime &mdash; a `StampBuf` owns its pixel
+/// buffer, so it can be drawn into and composited onto, e.g. to assemble a frame from
+/// several sprites before flushing it to a display.
+///
+/// Unavailable under the `progmem` feature: [`as_stamp`](StampBuf::as_stamp) would have
+/// to hand out a `Stamp` whose data pointer is wrapped in `ProgMem`, which assumes the
+/// pointee lives in program/flash memory, never true of this buffer's heap-allocated
+/// `Vec`.
+///
+/// # Examples
+///
+/// ```rust
+/// use stockbook::{Color, StampBuf};
+///
+/// let mut frame = StampBuf::new(4, 4);
+/// frame.set_color(1, 1, Color::White);
+///
+/// assert_eq!(unsafe { frame.as_stamp() }.get_color(1, 1), Color::White);
+/// assert_eq!(unsafe { frame.as_stamp() }.get_color(0, 0), Color::Black);
+/// ```
+#[cfg(not(feature = "progmem"))]
+#[derive(Debug, Clone)]
+pub struct StampBuf {
+    width: usize,
+    height: usize,
+    bits: Vec<u8>,
+}
+
+#[cfg(not(feature = "progmem"))]
+impl StampBuf {
+    /// Creates a new buffer of the given size, filled with [`Color::Black`].
+    pub fn new(width: usize, height: usize) -> Self {
+        let byte_len = (width * height).div_ceil(8);
+        Self {
+            width,
+            height,
+            bits: vec![0; byte_len],
+        }
+    }
+
+    /// Size of the buffer in pixels &mdash; width and height, or columns and rows.
+    #[inline]
+    pub fn size(&self) -> [usize; 2] {
+        [self.width, self.height]
+    }
+
+    fn is_within_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn get_color_at(&self, x: usize, y: usize) -> Color {
+        let idx = y * self.width + x;
+        let byte = self.bits[idx / 8];
+        let mask = 0b10000000 >> (idx % 8);
+
+        if byte & mask != 0 {
+            Color::White
+        } else {
+            Color::Black
+        }
+    }
+
+    /// Sets the color of the pixel at the given coordinate.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the coordinate is out of bounds.
+    pub fn set_color(&mut self, x: usize, y: usize, color: Color) {
+        assert!(self.is_within_bounds(x, y), "coordinate out of bounds");
+
+        let idx = y * self.width + x;
+        let mask = 0b10000000 >> (idx % 8);
+        let byte = &mut self.bits[idx / 8];
+
+        match color {
+            Color::White => *byte |= mask,
+            Color::Black => *byte &= !mask,
+        }
+    }
+
+    /// Sets every pixel of the buffer to the given color.
+    pub fn fill(&mut self, color: Color) {
+        let byte = match color {
+            Color::Black => 0x00,
+            Color::White => 0xFF,
+        };
+        self.bits.fill(byte);
+    }
+
+    /// Composites `src` onto this buffer with its top-left corner at `at`, treating
+    /// `transparent` as see-through. Any part of `src` that falls outside of this
+    /// buffer is silently clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp, StampBuf};
+    ///
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(2, 2, [0b10_01_00_00].as_ptr()) } };
+    /// # }
+    /// static SPRITE: Stamp = stamp!("sprite_2x2.png");
+    ///
+    /// let mut frame = StampBuf::new(3, 3);
+    /// frame.overlay(&SPRITE, (1, 1), Color::Black);
+    ///
+    /// assert_eq!(unsafe { frame.as_stamp() }.get_color(1, 1), Color::White);
+    /// assert_eq!(unsafe { frame.as_stamp() }.get_color(2, 1), Color::Black);
+    /// assert_eq!(unsafe { frame.as_stamp() }.get_color(1, 2), Color::Black);
+    /// assert_eq!(unsafe { frame.as_stamp() }.get_color(2, 2), Color::White);
+    ///
+    /// // Overlaying again past the edge is silently clipped, not a panic.
+    /// frame.overlay(&SPRITE, (2, 2), Color::Black);
+    /// ```
+    pub fn overlay(&mut self, src: &Stamp, at: (usize, usize), transparent: Color) {
+        let (at_x, at_y) = at;
+
+        for (x, y, color) in src.pixels() {
+            if color == transparent {
+                continue;
+            }
+
+            let (dst_x, dst_y) = (at_x + x, at_y + y);
+            if self.is_within_bounds(dst_x, dst_y) {
+                self.set_color(dst_x, dst_y, color);
+            }
+        }
+    }
+
+    /// Blits the `width x height` rectangle at `from` to `to`, both within this same
+    /// buffer. Handles overlapping source and destination rectangles correctly, by
+    /// walking rows (and columns) back-to-front whenever the destination is ahead of
+    /// the source, so that a pixel is never overwritten before it's been read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{Color, StampBuf};
+    ///
+    /// let mut buf = StampBuf::new(4, 1);
+    /// buf.set_color(0, 0, Color::White);
+    /// buf.set_color(1, 0, Color::Black);
+    /// buf.set_color(2, 0, Color::White);
+    /// buf.set_color(3, 0, Color::Black);
+    ///
+    /// // Shift the first 3 pixels one to the right. The source and destination
+    /// // rectangles overlap at x = 1..3, but every pixel still ends up reading its
+    /// // original, pre-shift value.
+    /// buf.copy_within((0, 0, 3, 1), (1, 0));
+    ///
+    /// let stamp = unsafe { buf.as_stamp() };
+    /// assert_eq!(stamp.get_color(0, 0), Color::White);
+    /// assert_eq!(stamp.get_color(1, 0), Color::White);
+    /// assert_eq!(stamp.get_color(2, 0), Color::Black);
+    /// assert_eq!(stamp.get_color(3, 0), Color::White);
+    /// ```
+    pub fn copy_within(&mut self, from: (usize, usize, usize, usize), to: (usize, usize)) {
+        let (from_x, from_y, width, height) = from;
+        let (to_x, to_y) = to;
+
+        let reverse_rows = to_y > from_y;
+        let reverse_cols = to_x > from_x;
+
+        for row in 0..height {
+            let row = if reverse_rows { height - 1 - row } else { row };
+
+            for col in 0..width {
+                let col = if reverse_cols { width - 1 - col } else { col };
+
+                let (src_x, src_y) = (from_x + col, from_y + row);
+                let (dst_x, dst_y) = (to_x + col, to_y + row);
+
+                if !self.is_within_bounds(src_x, src_y) || !self.is_within_bounds(dst_x, dst_y) {
+                    continue;
+                }
+
+                let color = self.get_color_at(src_x, src_y);
+                self.set_color(dst_x, dst_y, color);
+            }
+        }
+    }
+
+    /// Borrows this buffer's pixel data as a [`Stamp`], so the read-only API
+    /// ([`pixels`](Stamp::pixels), [`get_color`](Stamp::get_color), etc.) can be used
+    /// on it directly.
+    ///
+    /// # Safety
+    ///
+    /// The returned `Stamp` borrows this buffer's data through a raw pointer with no
+    /// lifetime tracking. The caller must ensure it does not outlive `self`, and that
+    /// `self` is not mutated (which may reallocate the underlying buffer) for as long
+    /// as it's in use.
+    pub unsafe fn as_stamp(&self) -> Stamp {
+        Stamp::from_raw(self.width, self.height, self.bits.as_ptr())
+    }
+}
+
+#[cfg(not(feature = "progmem"))]
+impl Stamp {
+    /// Copies this stamp's pixel data into a new, owned, mutable [`StampBuf`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(3, 2, [0b101_010_00].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_3x2.png");
+    ///
+    /// let mut buf = IMAGE.to_owned();
+    /// buf.set_color(0, 0, Color::Black);
+    ///
+    /// assert_eq!(IMAGE.get_color(0, 0), Color::White);
+    /// assert_eq!(unsafe { buf.as_stamp() }.get_color(0, 0), Color::Black);
+    /// ```
+    pub fn to_owned(&self) -> StampBuf {
+        let mut buf = StampBuf::new(self.width, self.height);
+
+        for (x, y, color) in self.pixels() {
+            buf.set_color(x, y, color);
+        }
+
+        buf
+    }
+}
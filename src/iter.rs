@@ -0,0 +1,224 @@
+//! Iterators over a [`Stamp`](crate::Stamp)'s pixels, rows, columns, and sub-rectangles.
+//!
+//! All of these are zero-copy views over the stamp's existing bit data, bounds-checked
+//! up front at construction rather than on every step.
+
+use crate::data::Bits;
+use crate::{Color, Stamp};
+
+/// Iterator over all pixels of a [`Stamp`](crate::Stamp), in _x_-then-_y_ order.
+/// Created by [`Stamp::pixels`](crate::Stamp::pixels).
+///
+/// This is the fast, fully sequential way to visit every pixel: unlike indexing via
+/// [`Stamp::get_color`], it never re-walks a compressed stamp's data from the start.
+#[derive(Debug, Clone)]
+pub struct Pixels<'a> {
+    width: usize,
+    pixel_count: usize,
+    index: usize,
+    bits: Bits<'a>,
+}
+
+impl<'a> Pixels<'a> {
+    pub(crate) fn new(stamp: &'a Stamp) -> Self {
+        Self {
+            width: stamp.width(),
+            pixel_count: stamp.pixel_count(),
+            index: 0,
+            bits: stamp.data.bits(),
+        }
+    }
+}
+
+impl<'a> Iterator for Pixels<'a> {
+    type Item = (usize, usize, Color);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.pixel_count {
+            return None;
+        }
+
+        let x = self.index % self.width;
+        let y = self.index / self.width;
+        // SAFETY: `self.index` is bounded by `self.pixel_count` above.
+        let color = if unsafe { self.bits.next_bit() } {
+            Color::White
+        } else {
+            Color::Black
+        };
+
+        self.index += 1;
+        Some((x, y, color))
+    }
+}
+
+/// Iterator over the rows of a [`Stamp`](crate::Stamp), top to bottom. Created by
+/// [`Stamp::rows`](crate::Stamp::rows).
+#[derive(Debug, Clone)]
+pub struct Rows<'a> {
+    stamp: &'a Stamp,
+    y: usize,
+}
+
+impl<'a> Rows<'a> {
+    pub(crate) fn new(stamp: &'a Stamp) -> Self {
+        Self { stamp, y: 0 }
+    }
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Row<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.stamp.height() {
+            return None;
+        }
+
+        let row = Row::new(self.stamp, self.y);
+        self.y += 1;
+        Some(row)
+    }
+}
+
+/// Iterator over the pixels of a single row of a [`Stamp`](crate::Stamp), left to
+/// right. Created by [`Stamp::rows`](crate::Stamp::rows) or
+/// [`Stamp::row`](crate::Stamp::row).
+#[derive(Debug, Clone)]
+pub struct Row<'a> {
+    stamp: &'a Stamp,
+    y: usize,
+    x: usize,
+}
+
+impl<'a> Row<'a> {
+    pub(crate) fn new(stamp: &'a Stamp, y: usize) -> Self {
+        Self { stamp, y, x: 0 }
+    }
+}
+
+impl<'a> Iterator for Row<'a> {
+    type Item = Color;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.x >= self.stamp.width() {
+            return None;
+        }
+
+        // SAFETY: `self.x` is bounded by `self.stamp.width()` above, and `self.y` was
+        // bounds-checked when this `Row` was constructed.
+        let color = unsafe { self.stamp.get_color_unchecked(self.x, self.y) };
+        self.x += 1;
+        Some(color)
+    }
+}
+
+/// Iterator over the columns of a [`Stamp`](crate::Stamp), left to right. Created by
+/// [`Stamp::columns`](crate::Stamp::columns).
+#[derive(Debug, Clone)]
+pub struct Columns<'a> {
+    stamp: &'a Stamp,
+    x: usize,
+}
+
+impl<'a> Columns<'a> {
+    pub(crate) fn new(stamp: &'a Stamp) -> Self {
+        Self { stamp, x: 0 }
+    }
+}
+
+impl<'a> Iterator for Columns<'a> {
+    type Item = Column<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.x >= self.stamp.width() {
+            return None;
+        }
+
+        let column = Column::new(self.stamp, self.x);
+        self.x += 1;
+        Some(column)
+    }
+}
+
+/// Iterator over the pixels of a single column of a [`Stamp`](crate::Stamp), top to
+/// bottom. Created by [`Stamp::columns`](crate::Stamp::columns) or
+/// [`Stamp::column`](crate::Stamp::column).
+#[derive(Debug, Clone)]
+pub struct Column<'a> {
+    stamp: &'a Stamp,
+    x: usize,
+    y: usize,
+}
+
+impl<'a> Column<'a> {
+    pub(crate) fn new(stamp: &'a Stamp, x: usize) -> Self {
+        Self { stamp, x, y: 0 }
+    }
+}
+
+impl<'a> Iterator for Column<'a> {
+    type Item = Color;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.stamp.height() {
+            return None;
+        }
+
+        // SAFETY: `self.y` is bounded by `self.stamp.height()` above, and `self.x` was
+        // bounds-checked when this `Column` was constructed.
+        let color = unsafe { self.stamp.get_color_unchecked(self.x, self.y) };
+        self.y += 1;
+        Some(color)
+    }
+}
+
+/// Iterator over the pixels of a bounded sub-rectangle of a [`Stamp`](crate::Stamp),
+/// in the same _x_-then-_y_ order as [`Pixels`]. Created by
+/// [`Stamp::region`](crate::Stamp::region).
+#[derive(Debug, Clone)]
+pub struct Region<'a> {
+    stamp: &'a Stamp,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    dx: usize,
+    dy: usize,
+}
+
+impl<'a> Region<'a> {
+    pub(crate) fn new(stamp: &'a Stamp, x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            stamp,
+            x,
+            y,
+            width,
+            height,
+            dx: 0,
+            dy: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Region<'a> {
+    type Item = (usize, usize, Color);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.width == 0 || self.dy >= self.height {
+            return None;
+        }
+
+        let (x, y) = (self.x + self.dx, self.y + self.dy);
+        // SAFETY: this region was bounds-checked against the stamp's dimensions when
+        // it was constructed, in `Stamp::region`.
+        let color = unsafe { self.stamp.get_color_unchecked(x, y) };
+
+        self.dx += 1;
+        if self.dx >= self.width {
+            self.dx = 0;
+            self.dy += 1;
+        }
+
+        Some((x, y, color))
+    }
+}
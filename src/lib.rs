@@ -77,7 +77,14 @@
 //!   [`avr_progmem::wrapper::ProgMem`](https://docs.rs/avr-progmem/latest/avr_progmem/wrapper/struct.ProgMem.html)s.
 //!   Combined with the `avr` target architecture, this allows you to keep most of
 //!   the data in program memory without the need to copy it to RAM. A no-op for
-//!   non-`avr` target architectures.
+//!   non-`avr` target architectures. Since it assumes every `Stamp`'s data lives in
+//!   program memory, it's incompatible with heap-backed buffers: `StampBuf` and the
+//!   `Stamp` methods that return one (`rotated90`, `to_owned`, etc.) are unavailable
+//!   under this feature.
+//! - **`pbm`** &mdash; lets [`stamp!`] recognize `.pbm` files (netpbm's `P1`/`P4`
+//!   formats) and parse them directly, without decoding them through the `image`
+//!   crate. The `image` crate remains a dependency of [`stamp!`], which still uses it
+//!   for every other format.
 //!
 //! ## Unstable features
 //!
@@ -88,15 +95,22 @@
 //! it is recommended to use the `nightly` toolchain, however functionality behind
 //! this feature is unstable and may change or stop compiling at any time.
 
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
 mod data;
 mod iter;
+mod rle;
+#[cfg(not(feature = "progmem"))]
+mod transform;
 
 use data::*;
 use iter::*;
 
+#[cfg(not(feature = "progmem"))]
+pub use data::StampBuf;
 pub use stockbook_stamp_macro::stamp;
 
 /// Rectangular, 1-bit, raster image.
@@ -243,6 +257,138 @@ impl Stamp {
         Pixels::new(self)
     }
 
+    /// Returns an iterator over the rows of this stamp, top to bottom. Each row is
+    /// itself an iterator over [`Color`], left to right.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(3, 2, [0b101_010_00].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_3x2.png");
+    ///
+    /// let rows: Vec<Vec<Color>> = IMAGE.rows().map(|row| row.collect()).collect();
+    ///
+    /// assert_eq!(rows, vec![
+    ///     vec![Color::White, Color::Black, Color::White],
+    ///     vec![Color::Black, Color::White, Color::Black],
+    /// ]);
+    /// ```
+    pub fn rows(&self) -> Rows<'_> {
+        Rows::new(self)
+    }
+
+    /// Returns an iterator over the pixels of row `y`, left to right.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `y` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(3, 2, [0b101_010_00].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_3x2.png");
+    ///
+    /// let row: Vec<Color> = IMAGE.row(1).collect();
+    ///
+    /// assert_eq!(row, vec![Color::Black, Color::White, Color::Black]);
+    /// ```
+    pub fn row(&self, y: usize) -> Row<'_> {
+        assert!(y < self.height, "row index out of bounds");
+        Row::new(self, y)
+    }
+
+    /// Returns an iterator over the columns of this stamp, left to right. Each column
+    /// is itself an iterator over [`Color`], top to bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(3, 2, [0b101_010_00].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_3x2.png");
+    ///
+    /// let columns: Vec<Vec<Color>> = IMAGE.columns().map(|column| column.collect()).collect();
+    ///
+    /// assert_eq!(columns, vec![
+    ///     vec![Color::White, Color::Black],
+    ///     vec![Color::Black, Color::White],
+    ///     vec![Color::White, Color::Black],
+    /// ]);
+    /// ```
+    pub fn columns(&self) -> Columns<'_> {
+        Columns::new(self)
+    }
+
+    /// Returns an iterator over the pixels of column `x`, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `x` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(3, 2, [0b101_010_00].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_3x2.png");
+    ///
+    /// let column: Vec<Color> = IMAGE.column(0).collect();
+    ///
+    /// assert_eq!(column, vec![Color::White, Color::Black]);
+    /// ```
+    pub fn column(&self, x: usize) -> Column<'_> {
+        assert!(x < self.width, "column index out of bounds");
+        Column::new(self, x)
+    }
+
+    /// Returns an iterator over the `width x height` sub-rectangle of this stamp
+    /// starting at `(x, y)`, in the same _x_-then-_y_ order as [`pixels`](Stamp::pixels).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the requested rectangle isn't fully within the bounds of
+    /// this stamp.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(3, 2, [0b101_010_00].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_3x2.png");
+    ///
+    /// let region: Vec<(usize, usize, Color)> = IMAGE.region(1, 0, 2, 2).collect();
+    ///
+    /// assert_eq!(region, vec![
+    ///     (1, 0, Color::Black), (2, 0, Color::White),
+    ///     (1, 1, Color::White), (2, 1, Color::Black),
+    /// ]);
+    ///
+    /// // A zero-width (or zero-height) region yields no pixels at all.
+    /// assert_eq!(IMAGE.region(1, 0, 0, 2).count(), 0);
+    /// ```
+    pub fn region(&self, x: usize, y: usize, width: usize, height: usize) -> Region<'_> {
+        assert!(x + width <= self.width && y + height <= self.height, "region out of bounds");
+        Region::new(self, x, y, width, height)
+    }
+
     /// Yields the color of the stamp at the provided coordinate. Panicking version of
     /// [`get_color_checked`](Stamp::get_color_checked).
     ///
@@ -324,16 +470,63 @@ impl Stamp {
     /// ```
     pub unsafe fn get_color_unchecked(&self, x: usize, y: usize) -> Color {
         let idx = y * self.width + x;
-        let byte = self.data.get_unchecked(idx / 8);
-        let mask = 0b10000000 >> (idx % 8);
 
-        if byte & mask != 0 {
+        if self.data.get_bit(idx) {
             Color::White
         } else {
             Color::Black
         }
     }
 
+    /// Writes the stamp out as a netpbm `P1` (ASCII PBM) image.
+    ///
+    /// This is the inverse of the `pbm` feature's `.pbm` support in [`stamp!`]: it's
+    /// handy for golden-file tests, or for dumping a stamp over a serial console while
+    /// debugging on-device.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Stamp};
+    ///
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(3, 2, [0b101_010_00].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_3x2.png");
+    ///
+    /// let mut pbm = String::new();
+    /// IMAGE.write_pbm(&mut pbm).unwrap();
+    ///
+    /// assert_eq!(pbm, "P1\n3 2\n0 1 0\n1 0 1\n");
+    /// ```
+    #[cfg(feature = "pbm")]
+    pub fn write_pbm(&self, buf: &mut impl core::fmt::Write) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        write!(buf, "P1\n{} {}\n", self.width, self.height)?;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if x > 0 {
+                    buf.write_char(' ')?;
+                }
+
+                // SAFETY: `x` and `y` are bounded by `self.width` and `self.height`.
+                //
+                // In PBM, a set bit means black, unlike Stockbook's white-when-set
+                // convention, hence the inversion here.
+                let bit = match unsafe { self.get_color_unchecked(x, y) } {
+                    Color::Black => '1',
+                    Color::White => '0',
+                };
+                buf.write_char(bit)?;
+            }
+            buf.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+
     /// Constructs a new stamp.
     ///
     /// You should not need to call this function directly. It is recommended to use the
@@ -381,6 +574,38 @@ impl Stamp {
             data: Data::from_raw(data),
         }
     }
+
+    /// Constructs a new stamp whose pixel data is RLE-compressed, rather than a flat
+    /// bit array.
+    ///
+    /// You should not need to call this function directly; it's what `stamp!`'s
+    /// `compress = rle` calls for you.
+    ///
+    /// # Safety
+    ///
+    /// `data` must point to a valid run-length-encoded byte stream: alternating
+    /// Black/White run lengths (each one or more bytes, `255` meaning "add 255 and
+    /// keep reading"), starting with a Black run, that together add up to at least
+    /// `width * height` bits. The same pointer-validity and, under `"progmem"`,
+    /// program-memory constraints as [`from_raw`](Stamp::from_raw) apply.
+    ///
+    /// # Examples
+    ///
+    /// Here, the dimensions of the stamp are 3x2, so 6 pixels in total; a Black run of
+    /// 2, a White run of 3, and a Black run of 1 add up to exactly that:
+    ///
+    /// ```rust
+    /// use stockbook::Stamp;
+    ///
+    /// let stamp = unsafe { Stamp::from_raw_rle(3, 2, [2, 3, 1].as_ptr()) };
+    /// ```
+    pub const unsafe fn from_raw_rle(width: usize, height: usize, data: *const u8) -> Self {
+        Self {
+            width,
+            height,
+            data: Data::from_raw_rle(data),
+        }
+    }
 }
 
 /// Color of a pixel.
@@ -0,0 +1,145 @@
+//! Decoding for the RLE representation a [`stamp!`](crate::stamp) can be compiled
+//! into via `compress = rle`, to save program memory on AVR and other
+//! flash-constrained targets.
+//!
+//! The stream is a sequence of alternating Black/White run lengths, always starting
+//! with a (possibly zero-length) Black run. Each run length is one or more bytes: a
+//! byte of `0..=254` is literal and terminates the length, while `255` means "add 255
+//! and read another length byte".
+
+use crate::data::Bytes;
+
+/// Reads the bit at `index` by walking runs from the start of the stream.
+///
+/// This is the only way to do random access into an RLE stream, so it costs O(`index`)
+/// rather than O(1); callers that need every bit should prefer a sequential
+/// [`RunCursor`] instead.
+pub(crate) unsafe fn bit_at(bytes: &Bytes, index: usize) -> bool {
+    let mut cursor = RunCursor::new();
+    loop {
+        // SAFETY: forwarded from this function's own safety contract: `index` must be
+        // in bounds for the stamp this RLE stream belongs to.
+        if cursor.position() == index {
+            return cursor.next_bit(bytes);
+        }
+        cursor.next_bit(bytes);
+    }
+}
+
+/// Sequential cursor over an RLE-encoded bit stream.
+#[derive(Debug, Clone)]
+pub(crate) struct RunCursor {
+    /// Color of the run currently being read; black for the very first run.
+    white: bool,
+    /// Bits remaining in the current run, including the one about to be yielded.
+    remaining: usize,
+    /// Byte offset of the next not-yet-read run-length byte.
+    byte_pos: usize,
+    /// Whether the first run length has been read yet (it's always Black, so unlike
+    /// every later run it must not flip the color on the way in).
+    started: bool,
+    /// Number of bits yielded so far.
+    position: usize,
+}
+
+impl RunCursor {
+    pub(crate) const fn new() -> Self {
+        Self {
+            white: false,
+            remaining: 0,
+            byte_pos: 0,
+            started: false,
+            position: 0,
+        }
+    }
+
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Yields the next bit (`true` is white) in sequence.
+    ///
+    /// # Safety
+    ///
+    /// The caller must not request more bits than the stream actually encodes.
+    pub(crate) unsafe fn next_bit(&mut self, bytes: &Bytes) -> bool {
+        while self.remaining == 0 {
+            self.remaining = self.read_run_length(bytes);
+            if self.started {
+                self.white = !self.white;
+            }
+            self.started = true;
+        }
+
+        self.remaining -= 1;
+        self.position += 1;
+        self.white
+    }
+
+    unsafe fn read_run_length(&mut self, bytes: &Bytes) -> usize {
+        let mut len = 0usize;
+        loop {
+            let byte = bytes.get_unchecked(self.byte_pos);
+            self.byte_pos += 1;
+            len += byte as usize;
+            if byte != 255 {
+                break;
+            }
+        }
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::Data;
+
+    // Builds a `Data::Rle` over `stream`, and decodes it both sequentially (via
+    // `Data::bits`) and by random access (via `Data::get_bit`), asserting both agree
+    // with `expected`.
+    fn assert_decodes_to(stream: &[u8], expected: &[bool]) {
+        // SAFETY: `stream` is a valid RLE encoding of `expected`, kept alive for the
+        // duration of this function.
+        let data = unsafe { Data::from_raw_rle(stream.as_ptr()) };
+
+        let mut bits = data.bits();
+        let sequential: Vec<bool> = (0..expected.len())
+            .map(|_| unsafe { bits.next_bit() })
+            .collect();
+        assert_eq!(sequential, expected);
+
+        let random_access: Vec<bool> = (0..expected.len()).map(|i| unsafe { data.get_bit(i) }).collect();
+        assert_eq!(random_access, expected);
+    }
+
+    #[test]
+    fn decodes_alternating_runs() {
+        // Black, Black, White, White, White, Black
+        assert_decodes_to(&[2, 3, 1], &[false, false, true, true, true, false]);
+    }
+
+    #[test]
+    fn decodes_a_zero_length_leading_black_run() {
+        assert_decodes_to(&[0, 2, 1], &[true, true, false]);
+    }
+
+    #[test]
+    fn decodes_a_run_length_split_across_continuation_bytes() {
+        let mut bits = vec![false; 300];
+        bits.extend([true, true]);
+        assert_decodes_to(&[255, 45, 2], &bits);
+    }
+
+    #[test]
+    fn random_access_does_not_require_reading_in_order() {
+        let stream = [2u8, 3, 1];
+        // SAFETY: `stream` is a valid RLE encoding of 6 bits, and outlives `data` below.
+        let data = unsafe { Data::from_raw_rle(stream.as_ptr()) };
+
+        // Indices are read out of order and repeatedly, unlike the sequential cursor.
+        assert!(unsafe { !data.get_bit(0) });
+        assert!(unsafe { data.get_bit(4) });
+        assert!(unsafe { !data.get_bit(1) });
+        assert!(unsafe { data.get_bit(4) });
+    }
+}
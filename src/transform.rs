@@ -0,0 +1,206 @@
+//! Affine transforms that produce a new, owned [`StampBuf`] from a [`Stamp`].
+
+use crate::{Stamp, StampBuf};
+
+impl Stamp {
+    /// Returns a copy of this stamp rotated 90° clockwise. Width and height are
+    /// swapped in the result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// // Only the top-right pixel is white.
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(2, 2, [0b0100_0000].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_2x2.png");
+    ///
+    /// let rotated = IMAGE.rotated90();
+    /// let rotated = unsafe { rotated.as_stamp() };
+    ///
+    /// // After a 90° clockwise rotation, it's the bottom-right pixel that's white.
+    /// assert_eq!(rotated.size(), [2, 2]);
+    /// assert_eq!(rotated.get_color(1, 1), Color::White);
+    /// assert_eq!(rotated.get_color(1, 0), Color::Black);
+    /// ```
+    pub fn rotated90(&self) -> StampBuf {
+        let mut buf = StampBuf::new(self.height(), self.width());
+
+        for (x, y, color) in self.pixels() {
+            buf.set_color(self.height() - 1 - y, x, color);
+        }
+
+        buf
+    }
+
+    /// Returns a copy of this stamp rotated 180°.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// // Only the top-right pixel is white.
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(2, 2, [0b0100_0000].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_2x2.png");
+    ///
+    /// let rotated = IMAGE.rotated180();
+    /// let rotated = unsafe { rotated.as_stamp() };
+    ///
+    /// // After a 180° rotation, it's the bottom-left pixel that's white.
+    /// assert_eq!(rotated.size(), [2, 2]);
+    /// assert_eq!(rotated.get_color(0, 1), Color::White);
+    /// assert_eq!(rotated.get_color(1, 0), Color::Black);
+    /// ```
+    pub fn rotated180(&self) -> StampBuf {
+        let mut buf = StampBuf::new(self.width(), self.height());
+
+        for (x, y, color) in self.pixels() {
+            buf.set_color(self.width() - 1 - x, self.height() - 1 - y, color);
+        }
+
+        buf
+    }
+
+    /// Returns a copy of this stamp rotated 270° clockwise (i.e. 90° counterclockwise).
+    /// Width and height are swapped in the result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// // Only the top-right pixel is white.
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(2, 2, [0b0100_0000].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_2x2.png");
+    ///
+    /// let rotated = IMAGE.rotated270();
+    /// let rotated = unsafe { rotated.as_stamp() };
+    ///
+    /// // After a 270° clockwise rotation, it's the top-left pixel that's white.
+    /// assert_eq!(rotated.size(), [2, 2]);
+    /// assert_eq!(rotated.get_color(0, 0), Color::White);
+    /// assert_eq!(rotated.get_color(1, 0), Color::Black);
+    /// ```
+    pub fn rotated270(&self) -> StampBuf {
+        let mut buf = StampBuf::new(self.height(), self.width());
+
+        for (x, y, color) in self.pixels() {
+            buf.set_color(y, self.width() - 1 - x, color);
+        }
+
+        buf
+    }
+
+    /// Returns a copy of this stamp mirrored along its vertical axis, i.e. left-right.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// // Only the top-right pixel is white.
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(2, 2, [0b0100_0000].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_2x2.png");
+    ///
+    /// let flipped = IMAGE.flipped_horizontal();
+    /// let flipped = unsafe { flipped.as_stamp() };
+    ///
+    /// // Mirrored left-right, it's the top-left pixel that's white.
+    /// assert_eq!(flipped.size(), [2, 2]);
+    /// assert_eq!(flipped.get_color(0, 0), Color::White);
+    /// assert_eq!(flipped.get_color(1, 0), Color::Black);
+    /// ```
+    pub fn flipped_horizontal(&self) -> StampBuf {
+        let mut buf = StampBuf::new(self.width(), self.height());
+
+        for (x, y, color) in self.pixels() {
+            buf.set_color(self.width() - 1 - x, y, color);
+        }
+
+        buf
+    }
+
+    /// Returns a copy of this stamp mirrored along its horizontal axis, i.e. top-bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// // Only the top-right pixel is white.
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(2, 2, [0b0100_0000].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_2x2.png");
+    ///
+    /// let flipped = IMAGE.flipped_vertical();
+    /// let flipped = unsafe { flipped.as_stamp() };
+    ///
+    /// // Mirrored top-bottom, it's the bottom-right pixel that's white.
+    /// assert_eq!(flipped.size(), [2, 2]);
+    /// assert_eq!(flipped.get_color(1, 1), Color::White);
+    /// assert_eq!(flipped.get_color(1, 0), Color::Black);
+    /// ```
+    pub fn flipped_vertical(&self) -> StampBuf {
+        let mut buf = StampBuf::new(self.width(), self.height());
+
+        for (x, y, color) in self.pixels() {
+            buf.set_color(x, self.height() - 1 - y, color);
+        }
+
+        buf
+    }
+
+    /// Returns the `width x height` rectangle of this stamp starting at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the requested rectangle isn't fully within the bounds of
+    /// this stamp.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stockbook::{stamp, Color, Stamp};
+    ///
+    /// // Only the top-right pixel is white.
+    /// # macro_rules! stamp {
+    /// #     ($path:literal) => { unsafe { Stamp::from_raw(2, 2, [0b0100_0000].as_ptr()) } };
+    /// # }
+    /// static IMAGE: Stamp = stamp!("image_2x2.png");
+    ///
+    /// let cropped = IMAGE.cropped(1, 0, 1, 2);
+    /// let cropped = unsafe { cropped.as_stamp() };
+    ///
+    /// assert_eq!(cropped.size(), [1, 2]);
+    /// assert_eq!(cropped.get_color(0, 0), Color::White);
+    /// assert_eq!(cropped.get_color(0, 1), Color::Black);
+    /// ```
+    pub fn cropped(&self, x: usize, y: usize, width: usize, height: usize) -> StampBuf {
+        assert!(
+            x + width <= self.width() && y + height <= self.height(),
+            "crop rectangle out of bounds"
+        );
+
+        let mut buf = StampBuf::new(width, height);
+
+        for dy in 0..height {
+            for dx in 0..width {
+                // SAFETY: `x + dx` and `y + dy` are within bounds per the assertion above.
+                let color = unsafe { self.get_color_unchecked(x + dx, y + dy) };
+                buf.set_color(dx, dy, color);
+            }
+        }
+
+        buf
+    }
+}
@@ -0,0 +1,169 @@
+//! Binarization of a luminance image into the 1-bit pixels a [`Stamp`] is made of.
+//!
+//! [`Stamp`]: https://docs.rs/stockbook/*/stockbook/struct.Stamp.html
+
+/// How a source image's luminance should be reduced to black/white pixels.
+pub enum Binarization {
+    /// Hard cutoff: luminance at or above `threshold` becomes white, the rest black.
+    Threshold(f32),
+    /// Floyd–Steinberg error-diffusion dithering.
+    FloydSteinberg {
+        /// Cutoff against which a pixel's accumulated value is compared.
+        threshold: f32,
+    },
+    /// Ordered dithering against a recursively-constructed Bayer matrix of `size x size`.
+    Bayer {
+        /// Side length of the Bayer matrix; always a power of two.
+        size: usize,
+        /// Cutoff against which a pixel's luminance is compared.
+        threshold: f32,
+    },
+}
+
+/// Plain hard-threshold binarization, with no error diffusion.
+pub fn threshold(luminance: &[f32], threshold: f32) -> Vec<bool> {
+    luminance.iter().map(|&l| l >= threshold).collect()
+}
+
+/// Floyd–Steinberg error-diffusion dithering, processed in raster order.
+///
+/// Only two rows of accumulated error are ever needed at a time: the one being
+/// written to (the current row, receiving the `right` weight) and the one below it
+/// (receiving `bottom-left`, `bottom`, and `bottom-right`). Error diffused past the
+/// last column or last row is simply dropped, which is the standard, unnormalized
+/// behavior of this algorithm at image edges.
+pub fn floyd_steinberg(luminance: &[f32], width: usize, height: usize, threshold: f32) -> Vec<bool> {
+    let mut bits = vec![false; luminance.len()];
+    let mut current_row = luminance[0..width].to_vec();
+    let mut next_row = vec![0.0; width];
+
+    for y in 0..height {
+        if y + 1 < height {
+            let next_start = (y + 1) * width;
+            next_row.copy_from_slice(&luminance[next_start..next_start + width]);
+        }
+
+        for x in 0..width {
+            let idx = y * width + x;
+            let old = current_row[x];
+            let new = if old >= threshold { 1.0 } else { 0.0 };
+            bits[idx] = new != 0.0;
+
+            let error = old - new;
+
+            if x + 1 < width {
+                current_row[x + 1] += error * 7.0 / 16.0;
+            }
+            if y + 1 < height {
+                if x > 0 {
+                    next_row[x - 1] += error * 3.0 / 16.0;
+                }
+                next_row[x] += error * 5.0 / 16.0;
+                if x + 1 < width {
+                    next_row[x + 1] += error * 1.0 / 16.0;
+                }
+            }
+        }
+
+        if y + 1 < height {
+            current_row.copy_from_slice(&next_row);
+        }
+    }
+
+    bits
+}
+
+/// Ordered dithering against a recursively-constructed Bayer matrix.
+pub fn bayer(luminance: &[f32], width: usize, height: usize, size: usize, threshold: f32) -> Vec<bool> {
+    let matrix = bayer_matrix(size);
+    let matrix_max = (size * size) as f32;
+
+    let mut bits = vec![false; luminance.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let idx = y * width + x;
+            // Normalize the matrix entry around `threshold` instead of comparing
+            // against a fixed 0.5, so `threshold` still has the expected meaning.
+            let bias = (matrix[y % size][x % size] as f32 / matrix_max) - 0.5;
+            bits[idx] = luminance[idx] + bias >= threshold;
+        }
+    }
+
+    bits
+}
+
+/// Recursively constructs the `n x n` Bayer matrix, for `n` a power of two.
+fn bayer_matrix(n: usize) -> Vec<Vec<u32>> {
+    if n == 1 {
+        return vec![vec![0]];
+    }
+
+    let half = bayer_matrix(n / 2);
+    let half_n = n / 2;
+    let mut matrix = vec![vec![0u32; n]; n];
+
+    for y in 0..half_n {
+        for x in 0..half_n {
+            let base = 4 * half[y][x];
+            matrix[y][x] = base;
+            matrix[y][x + half_n] = base + 2;
+            matrix[y + half_n][x] = base + 3;
+            matrix[y + half_n][x + half_n] = base + 1;
+        }
+    }
+
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_splits_at_the_cutoff() {
+        let luminance = [0.0, 0.3, 0.5, 0.7, 1.0];
+        assert_eq!(threshold(&luminance, 0.5), vec![false, false, true, true, true]);
+    }
+
+    #[test]
+    fn floyd_steinberg_diffuses_error_to_later_pixels() {
+        // A uniform mid-gray row: a hard threshold at 0.5 would make every pixel the
+        // same color, but error diffusion should alternate them to approximate the
+        // average.
+        let luminance = [0.4; 4];
+        let bits = floyd_steinberg(&luminance, 4, 1, 0.5);
+        assert!(bits.iter().any(|&b| b) && bits.iter().any(|&b| !b));
+    }
+
+    #[test]
+    fn floyd_steinberg_drops_error_past_the_last_row_and_column() {
+        // A single pixel has nowhere to diffuse its error to; this should simply not
+        // panic on an out-of-bounds write.
+        let luminance = [0.4];
+        let bits = floyd_steinberg(&luminance, 1, 1, 0.5);
+        assert_eq!(bits, vec![false]);
+    }
+
+    #[test]
+    fn bayer_matrix_2x2_is_the_classic_pattern() {
+        assert_eq!(bayer_matrix(2), vec![vec![0, 2], vec![3, 1]]);
+    }
+
+    #[test]
+    fn bayer_matrix_4x4_is_built_from_the_2x2_matrix() {
+        let matrix = bayer_matrix(4);
+        assert_eq!(matrix[0][0], 0);
+        assert_eq!(matrix[0][1], 8);
+        assert_eq!(matrix[1][0], 12);
+        assert_eq!(matrix[1][1], 4);
+    }
+
+    #[test]
+    fn bayer_thresholds_a_uniform_image_into_its_matrix_pattern() {
+        // Exactly at the threshold, so the bias from each matrix cell alone decides
+        // the outcome: low cells favor black, high cells favor white.
+        let luminance = [0.5; 4];
+        let bits = bayer(&luminance, 2, 2, 2, 0.5);
+        assert_eq!(bits, vec![false, true, true, false]);
+    }
+}
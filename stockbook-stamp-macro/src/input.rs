@@ -0,0 +1,141 @@
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitFloat, LitInt, LitStr, Token};
+
+use crate::dither::Binarization;
+
+/// The default threshold used when none is explicitly provided, either on its own or
+/// as the cutoff that a dithering algorithm compares accumulated/ordered error against.
+const DEFAULT_THRESHOLD: f32 = 0.5;
+
+/// Which compression scheme, if any, `stamp!` should pack the pixel data into.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Emit a flat bit array, for O(1) random access at runtime.
+    #[default]
+    None,
+    /// Emit an RLE-compressed byte stream, trading random-access speed for (usually)
+    /// less space in the final binary.
+    Rle,
+}
+
+/// Parsed arguments of the `stamp!` macro invocation.
+pub struct StampInput {
+    pub path: String,
+    pub path_span: Span,
+    pub binarization: Binarization,
+    /// Clockwise rotation to apply after binarization, in degrees (`0`, `90`, `180`
+    /// or `270`).
+    pub rotate: u16,
+    pub compress: Compression,
+}
+
+impl Parse for StampInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path_lit = input.parse::<LitStr>()?;
+        let path = path_lit.value();
+        let path_span = path_lit.span();
+
+        let mut threshold = None;
+        let mut dither = None;
+        let mut none = false;
+        let mut rotate = 0u16;
+        let mut compress = Compression::default();
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                // Allow a trailing comma after the last argument.
+                break;
+            }
+
+            let ident = input.parse::<Ident>()?;
+            match ident.to_string().as_str() {
+                "threshold" => {
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse::<LitFloat>()?;
+                    threshold = Some(value.base10_parse::<f32>()?);
+                }
+                "dither" => {
+                    input.parse::<Token![=]>()?;
+                    let mode = input.parse::<Ident>()?;
+                    dither = Some(parse_dither_mode(&mode)?);
+                }
+                "none" => none = true,
+                "rotate" => {
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse::<LitInt>()?;
+                    rotate = match value.base10_parse::<u16>()? {
+                        degrees @ (0 | 90 | 180 | 270) => degrees,
+                        _ => {
+                            let message = "`rotate` must be one of `0`, `90`, `180`, `270`";
+                            return Err(syn::Error::new(value.span(), message));
+                        }
+                    };
+                }
+                "compress" => {
+                    input.parse::<Token![=]>()?;
+                    let mode = input.parse::<Ident>()?;
+                    compress = match mode.to_string().as_str() {
+                        "rle" => Compression::Rle,
+                        other => {
+                            let message = format!("unknown compression scheme `{other}`");
+                            return Err(syn::Error::new(mode.span(), message));
+                        }
+                    };
+                }
+                other => {
+                    let message = format!("unknown `stamp!` argument `{other}`");
+                    return Err(syn::Error::new(ident.span(), message));
+                }
+            }
+        }
+
+        if none && dither.is_some() {
+            let message = "`none` cannot be combined with `dither`";
+            return Err(syn::Error::new(path_span, message));
+        }
+
+        let binarization = match dither {
+            Some(mode) => mode.with_threshold(threshold.unwrap_or(DEFAULT_THRESHOLD)),
+            None => Binarization::Threshold(threshold.unwrap_or(DEFAULT_THRESHOLD)),
+        };
+
+        Ok(StampInput {
+            path,
+            path_span,
+            binarization,
+            rotate,
+            compress,
+        })
+    }
+}
+
+/// Intermediate representation of a `dither = ...` argument, before the threshold
+/// (which may come from a separate `threshold = ...` argument) has been attached.
+enum DitherMode {
+    FloydSteinberg,
+    Bayer(usize),
+}
+
+impl DitherMode {
+    fn with_threshold(self, threshold: f32) -> Binarization {
+        match self {
+            DitherMode::FloydSteinberg => Binarization::FloydSteinberg { threshold },
+            DitherMode::Bayer(size) => Binarization::Bayer { size, threshold },
+        }
+    }
+}
+
+fn parse_dither_mode(ident: &Ident) -> syn::Result<DitherMode> {
+    match ident.to_string().as_str() {
+        "FloydSteinberg" => Ok(DitherMode::FloydSteinberg),
+        "Bayer2x2" => Ok(DitherMode::Bayer(2)),
+        "Bayer4x4" => Ok(DitherMode::Bayer(4)),
+        "Bayer8x8" => Ok(DitherMode::Bayer(8)),
+        other => {
+            let message = format!("unknown dithering mode `{other}`");
+            Err(syn::Error::new(ident.span(), message))
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Implementation of the [`stamp!`](https://docs.rs/stockbook/*/stockbook/macro.stamp.html)
+//! proc-macro. See the `stockbook` crate for user-facing documentation.
+
+mod dither;
+mod input;
+mod pack;
+#[cfg(feature = "pbm")]
+mod pbm;
+mod rle;
+mod rotate;
+
+use std::path::Path;
+
+use proc_macro::TokenStream;
+use quote::quote;
+
+use dither::Binarization;
+use input::{Compression, StampInput};
+
+/// Includes an image file as a [`Stamp`](https://docs.rs/stockbook/*/stockbook/struct.Stamp.html),
+/// binarizing it at compile time.
+///
+/// ```text
+/// stamp!("image.png")
+/// stamp!("image.png", threshold = 0.4)
+/// stamp!("image.png", none)
+/// stamp!("image.png", dither = FloydSteinberg)
+/// stamp!("image.png", dither = Bayer4x4)
+/// stamp!("image.png", dither = Bayer4x4, threshold = 0.6)
+/// stamp!("image.png", rotate = 90)
+/// stamp!("image.png", compress = rle)
+/// ```
+///
+/// The path is resolved relative to the crate's manifest directory (unlike
+/// [`include_bytes!`], which resolves it relative to the invoking source file).
+///
+/// By default (and under `none`), each source pixel's luminance is reduced to a
+/// single bit with a hard cutoff at `threshold`, which itself defaults to `0.5`.
+/// `dither = FloydSteinberg` diffuses the resulting quantization error to
+/// not-yet-visited neighbors instead, and `dither = Bayer2x2`/`Bayer4x4`/`Bayer8x8`
+/// compares luminance against a fixed recursively-constructed threshold matrix. Both
+/// dithering modes still honor an explicit `threshold`.
+///
+/// `rotate = 90`/`180`/`270` bakes a clockwise rotation (matching
+/// `Stamp::rotated90`/`rotated180`/`rotated270`) into the emitted data, with no
+/// runtime cost.
+///
+/// `compress = rle` run-length encodes the emitted data instead of packing it flat,
+/// which usually shrinks the binary at the cost of O(index) (rather than O(1)) random
+/// pixel access at runtime, as each lookup walks the runs from the start; sequential
+/// access through `Stamp::pixels` stays cheap. See `Stamp::from_raw_rle`.
+#[proc_macro]
+pub fn stamp(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as StampInput);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = Path::new(&manifest_dir).join(&input.path);
+
+    #[cfg(feature = "pbm")]
+    if path.extension().is_some_and(|ext| ext == "pbm") {
+        return stamp_from_pbm(&path, input.path_span, input.rotate, input.compress);
+    }
+
+    let image = match image::open(&path) {
+        Ok(image) => image,
+        Err(error) => {
+            let message = format!("failed to open image at `{}`: {error}", path.display());
+            return syn::Error::new(input.path_span, message)
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let width = image.width() as usize;
+    let height = image.height() as usize;
+    let luma = image.to_luma32f();
+
+    let luminance: Vec<f32> = luma.pixels().map(|p| p.0[0]).collect();
+
+    let bits = match input.binarization {
+        Binarization::Threshold(threshold) => dither::threshold(&luminance, threshold),
+        Binarization::FloydSteinberg { threshold } => {
+            dither::floyd_steinberg(&luminance, width, height, threshold)
+        }
+        Binarization::Bayer { size, threshold } => {
+            dither::bayer(&luminance, width, height, size, threshold)
+        }
+    };
+
+    stamp_from_bits(bits, width, height, input.rotate, input.compress)
+}
+
+/// Reads a `.pbm` file directly into a [`Stamp`](https://docs.rs/stockbook/*/stockbook/struct.Stamp.html),
+/// without going through the `image` crate.
+#[cfg(feature = "pbm")]
+fn stamp_from_pbm(
+    path: &Path,
+    path_span: proc_macro2::Span,
+    rotate: u16,
+    compress: Compression,
+) -> TokenStream {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            let message = format!("failed to read PBM file at `{}`: {error}", path.display());
+            return syn::Error::new(path_span, message).to_compile_error().into();
+        }
+    };
+
+    let image = match pbm::parse(&bytes) {
+        Ok(image) => image,
+        Err(error) => {
+            let message = format!("failed to parse PBM file at `{}`: {error}", path.display());
+            return syn::Error::new(path_span, message).to_compile_error().into();
+        }
+    };
+
+    stamp_from_bits(image.bits, image.width, image.height, rotate, compress)
+}
+
+/// Applies the `rotate = ..` argument (if any) and packs the result into the byte
+/// layout (flat or RLE-compressed, per `compress`) common to every `stamp!` code path.
+fn stamp_from_bits(
+    bits: Vec<bool>,
+    width: usize,
+    height: usize,
+    rotate_degrees: u16,
+    compress: Compression,
+) -> TokenStream {
+    let (bits, width, height) = rotate::rotate(&bits, width, height, rotate_degrees);
+
+    match compress {
+        Compression::None => {
+            let bytes = pack::pack_bits(&bits);
+            stamp_from_raw(width, height, &bytes)
+        }
+        Compression::Rle => {
+            let bytes = rle::encode(&bits);
+            stamp_from_raw_rle(width, height, &bytes)
+        }
+    }
+}
+
+/// Emits the `Stamp::from_raw` call common to every uncompressed `stamp!` code path.
+fn stamp_from_raw(width: usize, height: usize, bytes: &[u8]) -> TokenStream {
+    let byte_literals = bytes.iter().map(|byte| quote!(#byte));
+
+    let expanded = quote! {
+        unsafe {
+            ::stockbook::Stamp::from_raw(
+                #width,
+                #height,
+                [#(#byte_literals),*].as_ptr(),
+            )
+        }
+    };
+
+    expanded.into()
+}
+
+/// Emits the `Stamp::from_raw_rle` call common to every `compress = rle` code path.
+fn stamp_from_raw_rle(width: usize, height: usize, bytes: &[u8]) -> TokenStream {
+    let byte_literals = bytes.iter().map(|byte| quote!(#byte));
+
+    let expanded = quote! {
+        unsafe {
+            ::stockbook::Stamp::from_raw_rle(
+                #width,
+                #height,
+                [#(#byte_literals),*].as_ptr(),
+            )
+        }
+    };
+
+    expanded.into()
+}
@@ -0,0 +1,16 @@
+//! Packing of individual pixel bits into the byte layout [`Stamp`] expects.
+//!
+//! [`Stamp`]: https://docs.rs/stockbook/*/stockbook/struct.Stamp.html
+
+/// Packs `true`/`false` pixel bits into bytes, MSB-first, matching
+/// `Stamp::get_color_unchecked`'s indexing (`idx / 8`, `0b10000000 >> (idx % 8)`).
+pub fn pack_bits(bits: &[bool]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (i, &bit)| if bit { byte | (0b10000000 >> i) } else { byte })
+        })
+        .collect()
+}
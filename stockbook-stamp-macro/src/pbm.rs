@@ -0,0 +1,190 @@
+//! A minimal netpbm PBM (`P1`/`P4`) reader, used as a lightweight alternative to the
+//! `image` crate for the [`pbm`](crate) feature.
+//!
+//! PBM is a near-exact on-disk match for [`Stamp`](https://docs.rs/stockbook/*/stockbook/struct.Stamp.html)'s
+//! bit layout, so images in this format don't need to be decoded through a general
+//! purpose image-decoding crate.
+
+/// A decoded PBM bitmap: dimensions plus one `bool` per pixel (`true` is white, to
+/// match [`Color::White`](https://docs.rs/stockbook/*/stockbook/enum.Color.html)).
+pub struct PbmImage {
+    pub width: usize,
+    pub height: usize,
+    pub bits: Vec<bool>,
+}
+
+/// Parses a PBM file (`P1` ASCII or `P4` binary), returning an error message on
+/// malformed input.
+pub fn parse(bytes: &[u8]) -> Result<PbmImage, String> {
+    let mut cursor = Cursor { bytes, pos: 0 };
+
+    let magic = cursor.take(2).ok_or("unexpected end of file while reading magic number")?;
+    let (width, height) = parse_header(&mut cursor)?;
+
+    let bits = match magic {
+        b"P1" => parse_ascii_bitmap(&mut cursor, width, height)?,
+        b"P4" => {
+            // Exactly one whitespace byte separates the header from the binary body.
+            cursor.advance();
+            parse_binary_bitmap(&mut cursor, width, height)?
+        }
+        _ => {
+            let magic = String::from_utf8_lossy(magic);
+            return Err(format!("unsupported PBM magic number `{magic}`, expected `P1` or `P4`"));
+        }
+    };
+
+    Ok(PbmImage { width, height, bits })
+}
+
+/// A byte cursor over PBM source data, tracking the read position.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos + n)?;
+        self.pos += n;
+        Some(slice)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn advance(&mut self) {
+        self.pos += 1;
+    }
+
+    /// Skips whitespace and `#`-prefixed comments, which run to the end of the line.
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(byte) = self.peek() {
+            if byte == b'#' {
+                while let Some(byte) = self.peek() {
+                    self.advance();
+                    if byte == b'\n' {
+                        break;
+                    }
+                }
+            } else if byte.is_ascii_whitespace() {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Reads a whitespace/comment-delimited ASCII decimal number.
+    fn read_number(&mut self) -> Result<usize, String> {
+        self.skip_whitespace_and_comments();
+
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.advance();
+        }
+
+        if self.pos == start {
+            return Err("expected a decimal number in PBM header".to_string());
+        }
+
+        let digits = std::str::from_utf8(&self.bytes[start..self.pos]).expect("ASCII digits are valid UTF-8");
+        digits.parse().map_err(|_| "PBM dimension out of range".to_string())
+    }
+}
+
+fn parse_header(cursor: &mut Cursor<'_>) -> Result<(usize, usize), String> {
+    let width = cursor.read_number()?;
+    let height = cursor.read_number()?;
+    Ok((width, height))
+}
+
+/// Parses the `P1` bitmap body: whitespace-separated `0`/`1` ASCII digits.
+fn parse_ascii_bitmap(cursor: &mut Cursor<'_>, width: usize, height: usize) -> Result<Vec<bool>, String> {
+    let mut bits = Vec::with_capacity(width * height);
+
+    for _ in 0..width * height {
+        cursor.skip_whitespace_and_comments();
+        match cursor.peek() {
+            // In PBM, a set bit means black, unlike Stockbook's white-when-set convention.
+            Some(b'0') => bits.push(true),
+            Some(b'1') => bits.push(false),
+            _ => return Err("expected `0` or `1` in P1 bitmap body".to_string()),
+        }
+        cursor.advance();
+    }
+
+    Ok(bits)
+}
+
+/// Parses the `P4` bitmap body: MSB-first bits, each row padded to a whole byte.
+fn parse_binary_bitmap(cursor: &mut Cursor<'_>, width: usize, height: usize) -> Result<Vec<bool>, String> {
+    let row_bytes = width.div_ceil(8);
+    let mut bits = Vec::with_capacity(width * height);
+
+    for _ in 0..height {
+        let row = cursor
+            .take(row_bytes)
+            .ok_or("unexpected end of file while reading P4 bitmap body")?;
+
+        for x in 0..width {
+            let byte = row[x / 8];
+            let mask = 0b10000000 >> (x % 8);
+            // In PBM, a set bit means black, unlike Stockbook's white-when-set convention.
+            bits.push(byte & mask == 0);
+        }
+    }
+
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ascii_pbm() {
+        // `1` is a set bit, i.e. black.
+        let pbm = b"P1\n# a comment\n3 2\n0 1 0\n1 0 1\n";
+        let image = parse(pbm).unwrap();
+
+        assert_eq!(image.width, 3);
+        assert_eq!(image.height, 2);
+        assert_eq!(image.bits, vec![true, false, true, false, true, false]);
+    }
+
+    #[test]
+    fn parses_binary_pbm_with_row_padding() {
+        // 3x2 image, each row padded to 1 byte; set bits are black (`false`).
+        let mut pbm = b"P4\n3 2\n".to_vec();
+        pbm.push(0b010_00000); // row 0: white, black, white
+        pbm.push(0b101_00000); // row 1: black, white, black
+        let image = parse(&pbm).unwrap();
+
+        assert_eq!(image.width, 3);
+        assert_eq!(image.height, 2);
+        assert_eq!(image.bits, vec![true, false, true, false, true, false]);
+    }
+
+    #[test]
+    fn rejects_unknown_magic_number() {
+        assert!(parse(b"P6\n1 1\n").is_err());
+    }
+
+    #[test]
+    fn ascii_and_binary_pbm_decode_the_same_image_identically() {
+        let ascii = b"P1\n3 2\n0 1 0\n1 0 1\n";
+
+        let mut binary = b"P4\n3 2\n".to_vec();
+        binary.push(0b010_00000); // row 0: white, black, white
+        binary.push(0b101_00000); // row 1: black, white, black
+
+        let ascii_image = parse(ascii).unwrap();
+        let binary_image = parse(&binary).unwrap();
+
+        assert_eq!(ascii_image.width, binary_image.width);
+        assert_eq!(ascii_image.height, binary_image.height);
+        assert_eq!(ascii_image.bits, binary_image.bits);
+    }
+}
@@ -0,0 +1,58 @@
+//! Encoding bits into the RLE stream `Stamp::from_raw_rle` expects, for `stamp!`'s
+//! `compress = rle` argument.
+//!
+//! See `stockbook::rle` for the format: alternating Black/White run lengths, each one
+//! or more bytes (`255` meaning "add 255 and keep reading"), always starting with a
+//! (possibly zero-length) Black run.
+
+/// Encodes `bits` (`true` is white) into an RLE byte stream.
+pub fn encode(bits: &[bool]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut white = false; // runs start Black
+    let mut run_len = 0usize;
+
+    for &bit in bits {
+        if bit == white {
+            run_len += 1;
+        } else {
+            push_run_length(&mut bytes, run_len);
+            white = bit;
+            run_len = 1;
+        }
+    }
+    push_run_length(&mut bytes, run_len);
+
+    bytes
+}
+
+fn push_run_length(bytes: &mut Vec<u8>, mut len: usize) {
+    while len >= 255 {
+        bytes.push(255);
+        len -= 255;
+    }
+    bytes.push(len as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_alternating_runs() {
+        // Black, Black, White, White, White, Black
+        let bits = [false, false, true, true, true, false];
+        assert_eq!(encode(&bits), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn starts_with_a_zero_length_black_run_when_the_image_starts_white() {
+        let bits = [true, true, false];
+        assert_eq!(encode(&bits), vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn splits_runs_longer_than_254_across_continuation_bytes() {
+        let bits = vec![false; 300];
+        assert_eq!(encode(&bits), vec![255, 45]);
+    }
+}
@@ -0,0 +1,76 @@
+//! Compile-time rotation of a binarized bit grid, for `stamp!`'s `rotate = ..` argument.
+//!
+//! Mirrors the runtime `Stamp::rotated90`/`rotated180`/`rotated270` methods, but bakes
+//! the rotation into the emitted byte array instead of performing it at runtime.
+
+/// Rotates a `width x height` grid of bits clockwise by `degrees` (`0`, `90`, `180` or
+/// `270`), returning the rotated bits along with the new `(width, height)`.
+pub fn rotate(bits: &[bool], width: usize, height: usize, degrees: u16) -> (Vec<bool>, usize, usize) {
+    match degrees {
+        90 => {
+            let mut rotated = vec![false; bits.len()];
+            for y in 0..height {
+                for x in 0..width {
+                    rotated[x * height + (height - 1 - y)] = bits[y * width + x];
+                }
+            }
+            (rotated, height, width)
+        }
+        180 => {
+            let mut rotated = vec![false; bits.len()];
+            for y in 0..height {
+                for x in 0..width {
+                    let dst_x = width - 1 - x;
+                    let dst_y = height - 1 - y;
+                    rotated[dst_y * width + dst_x] = bits[y * width + x];
+                }
+            }
+            (rotated, width, height)
+        }
+        270 => {
+            let mut rotated = vec![false; bits.len()];
+            for y in 0..height {
+                for x in 0..width {
+                    rotated[(width - 1 - x) * height + y] = bits[y * width + x];
+                }
+            }
+            (rotated, height, width)
+        }
+        _ => (bits.to_vec(), width, height),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2x2 grid with only the top-right bit set.
+    const GRID: [bool; 4] = [false, true, false, false];
+
+    #[test]
+    fn rotate_0_is_identity() {
+        let (bits, width, height) = rotate(&GRID, 2, 2, 0);
+        assert_eq!((bits, width, height), (GRID.to_vec(), 2, 2));
+    }
+
+    #[test]
+    fn rotate_90_moves_top_right_to_bottom_right() {
+        let (bits, width, height) = rotate(&GRID, 2, 2, 90);
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(bits, vec![false, false, false, true]);
+    }
+
+    #[test]
+    fn rotate_180_moves_top_right_to_bottom_left() {
+        let (bits, width, height) = rotate(&GRID, 2, 2, 180);
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(bits, vec![false, false, true, false]);
+    }
+
+    #[test]
+    fn rotate_270_moves_top_right_to_top_left() {
+        let (bits, width, height) = rotate(&GRID, 2, 2, 270);
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(bits, vec![true, false, false, false]);
+    }
+}